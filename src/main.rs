@@ -1,7 +1,9 @@
 extern crate smallbitvec;
 
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::io::{self, Read, Write};
 
 use smallbitvec::SmallBitVec;
 
@@ -30,29 +32,554 @@ impl Ord for Node {
 
 impl PartialOrd for Node {
     fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
 pub struct HuffmanCode {
     pub code_table: HashMap<char, String>,
     pub compressed: SmallBitVec,
+    /// Root of the tree the codes were built from, retained so that
+    /// `huffman_decode` can walk it bit-by-bit without inverting the table.
+    root: Node,
+    /// Number of symbols in the original input, used to stop decoding before
+    /// any trailing padding bits in `compressed`.
+    num_symbols: usize,
 }
 
 pub fn huffman_code(s: &str) -> HuffmanCode {
     let freq_map = build_freq_map(s);
     let tree = build_huffman_tree(&freq_map);
-    let code_table = build_code_table(tree);
+    let code_table = build_code_table(&tree);
     let compressed = compress(s, &code_table);
 
     HuffmanCode {
-        code_table: code_table,
-        compressed: compressed,
+        code_table,
+        compressed,
+        root: tree,
+        num_symbols: s.chars().count(),
+    }
+}
+
+/// Like `huffman_code`, but assigns *canonical* codes: the tree only fixes
+/// each symbol's code length, after which codes are derived deterministically
+/// from the lengths alone. The resulting table is identical on both ends given
+/// just the per-symbol lengths. `to_bytes` does not make use of this: it still
+/// writes every symbol's full code bits, exactly as for `huffman_code`.
+pub fn huffman_code_canonical(s: &str) -> HuffmanCode {
+    let freq_map = build_freq_map(s);
+    let tree = build_huffman_tree(&freq_map);
+    // Read each symbol's code length off the tree, then discard the tree.
+    let lengths: HashMap<char, usize> = build_code_table(&tree)
+        .into_iter()
+        .map(|(sym, code)| (sym, code.len()))
+        .collect();
+    let code_table = canonical_code_table(&lengths);
+    let compressed = compress(s, &code_table);
+    let root = build_tree_from_codes(&code_table);
+
+    HuffmanCode {
+        code_table,
+        compressed,
+        root,
+        num_symbols: s.chars().count(),
     }
 }
 
 pub fn huffman_decode(huffman_code: &HuffmanCode) -> String {
-    String::new()
+    let mut decoded = String::new();
+
+    // A tree consisting of a single leaf has no branch bits; each symbol was
+    // encoded as the single bit "0", so just emit the leaf once per symbol.
+    if let NodeType::Leaf(sym) = huffman_code.root.data {
+        for _ in 0..huffman_code.num_symbols {
+            decoded.push(sym);
+        }
+        return decoded;
+    }
+
+    let mut node = &huffman_code.root;
+    let mut emitted = 0;
+    for bit in huffman_code.compressed.iter() {
+        node = match node.data {
+            NodeType::Internal { ref left_child, ref right_child } => {
+                // Left on 0/false, right on 1/true.
+                if bit { &**right_child } else { &**left_child }
+            },
+            NodeType::Leaf(_) => unreachable!(),
+        };
+        if let NodeType::Leaf(sym) = node.data {
+            decoded.push(sym);
+            node = &huffman_code.root;
+            emitted += 1;
+            if emitted == huffman_code.num_symbols {
+                break;
+            }
+        }
+    }
+
+    decoded
+}
+
+impl HuffmanCode {
+    /// Serialize into a compact, self-describing buffer that `from_bytes` can
+    /// round-trip with no other state: a header listing every symbol and its
+    /// code, the number of encoded symbols, and the packed bitstream.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        // (a) Header: the symbol set together with the bit pattern of each
+        // code, so the decoder can rebuild the tree from scratch.
+        out.extend_from_slice(&(self.code_table.len() as u32).to_le_bytes());
+        for (sym, code) in &self.code_table {
+            out.extend_from_slice(&(*sym as u32).to_le_bytes());
+            out.push(code.len() as u8);
+            out.extend_from_slice(&pack_bits(code.chars().map(|c| c == '1')));
+        }
+
+        // (b) Number of encoded symbols, so decoding stops before padding.
+        out.extend_from_slice(&(self.num_symbols as u64).to_le_bytes());
+
+        // (c) Packed bitstream. The final byte is zero-padded; record how many
+        // of its bits are real so `from_bytes` can recover the exact length.
+        let last_bits = if self.compressed.is_empty() {
+            0u8
+        } else {
+            match (self.compressed.len() % 8) as u8 {
+                0 => 8,
+                rem => rem,
+            }
+        };
+        out.push(last_bits);
+        out.extend_from_slice(&pack_bits(self.compressed.iter()));
+
+        out
+    }
+
+    /// Rebuild a `HuffmanCode` purely from the buffer produced by `to_bytes`,
+    /// reconstructing the decoding tree from the header so that
+    /// `huffman_decode` works with no other state.
+    pub fn from_bytes(bytes: &[u8]) -> HuffmanCode {
+        let mut pos = 0;
+
+        // (a) Header.
+        let num_distinct = read_u32(bytes, &mut pos) as usize;
+        let mut code_table = HashMap::new();
+        for _ in 0..num_distinct {
+            let sym = char::from_u32(read_u32(bytes, &mut pos))
+                .expect("header contained an invalid code point");
+            let code_len = bytes[pos] as usize;
+            pos += 1;
+            let num_code_bytes = code_len.div_ceil(8);
+            let code_bytes = &bytes[pos..pos + num_code_bytes];
+            pos += num_code_bytes;
+            let mut code = String::with_capacity(code_len);
+            for i in 0..code_len {
+                code.push(if bit_at(code_bytes, i) { '1' } else { '0' });
+            }
+            code_table.insert(sym, code);
+        }
+
+        // (b) Number of encoded symbols.
+        let num_symbols = read_u64(bytes, &mut pos) as usize;
+
+        // (c) Packed bitstream.
+        let last_bits = bytes[pos] as usize;
+        pos += 1;
+        let packed = &bytes[pos..];
+        let total_bits = if packed.is_empty() {
+            0
+        } else {
+            (packed.len() - 1) * 8 + last_bits
+        };
+        let mut compressed = SmallBitVec::new();
+        for i in 0..total_bits {
+            compressed.push(bit_at(packed, i));
+        }
+
+        let root = build_tree_from_codes(&code_table);
+        HuffmanCode {
+            code_table,
+            compressed,
+            root,
+            num_symbols,
+        }
+    }
+}
+
+/// Pack a stream of bits into bytes, most-significant-bit first, zero-padding
+/// the final byte.
+fn pack_bits<I: Iterator<Item = bool>>(bits: I) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut cur = 0u8;
+    let mut filled = 0;
+    for bit in bits {
+        if bit {
+            cur |= 1 << (7 - filled);
+        }
+        filled += 1;
+        if filled == 8 {
+            bytes.push(cur);
+            cur = 0;
+            filled = 0;
+        }
+    }
+    if filled > 0 {
+        bytes.push(cur);
+    }
+    bytes
+}
+
+/// Read the `i`th bit (most-significant-bit first) out of a packed byte slice.
+fn bit_at(bytes: &[u8], i: usize) -> bool {
+    (bytes[i / 8] >> (7 - (i % 8))) & 1 == 1
+}
+
+fn read_u32(bytes: &[u8], pos: &mut usize) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&bytes[*pos..*pos + 4]);
+    *pos += 4;
+    u32::from_le_bytes(buf)
+}
+
+fn read_u64(bytes: &[u8], pos: &mut usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[*pos..*pos + 8]);
+    *pos += 8;
+    u64::from_le_bytes(buf)
+}
+
+/// Rebuild a decoding tree from a symbol-to-code table, creating internal
+/// nodes as each code is threaded in bit-by-bit.
+fn build_tree_from_codes(table: &HashMap<char, String>) -> Node {
+    // A single-symbol input is encoded with the one-bit code "0"; its tree is
+    // just the lone leaf.
+    if table.len() == 1 {
+        let sym = *table.keys().next().unwrap();
+        return Node { freq: 0, data: NodeType::Leaf(sym) };
+    }
+
+    // An index-linked arena of partial nodes: slot 0 is the root, and internal
+    // nodes are carved out on demand as each code is threaded in. Linking by
+    // `usize` (as `build_flat_tree_from_codes` does) avoids reborrowing a `&mut`
+    // across loop iterations, which the borrow checker rejects.
+    struct Partial {
+        symbol: Option<char>,
+        left: Option<usize>,
+        right: Option<usize>,
+    }
+
+    let mut arena = vec![Partial { symbol: None, left: None, right: None }];
+    for (&sym, code) in table {
+        let mut cur = 0;
+        let bits: Vec<bool> = code.chars().map(|c| c == '1').collect();
+        for (i, bit) in bits.iter().enumerate() {
+            let child = if *bit { arena[cur].right } else { arena[cur].left };
+            cur = match child {
+                Some(existing) => existing,
+                None => {
+                    let idx = arena.len();
+                    arena.push(Partial { symbol: None, left: None, right: None });
+                    if *bit {
+                        arena[cur].right = Some(idx);
+                    } else {
+                        arena[cur].left = Some(idx);
+                    }
+                    idx
+                },
+            };
+            if i + 1 == bits.len() {
+                arena[cur].symbol = Some(sym);
+            }
+        }
+    }
+
+    fn finalize(arena: &[Partial], idx: usize) -> Node {
+        match arena[idx].symbol {
+            Some(sym) => Node { freq: 0, data: NodeType::Leaf(sym) },
+            None => Node {
+                freq: 0,
+                data: NodeType::Internal {
+                    left_child: Box::new(finalize(arena, arena[idx].left.unwrap())),
+                    right_child: Box::new(finalize(arena, arena[idx].right.unwrap())),
+                },
+            },
+        }
+    }
+
+    finalize(&arena, 0)
+}
+
+/// A byte coder has at most 256 leaves, and a full binary tree over `n` leaves
+/// has `2 * n - 1` nodes, so the whole tree fits in a fixed arena of this size.
+const MAX_NODES: usize = 256 * 2 - 1;
+
+/// A tree node living in a flat arena: instead of owning `Box` children it
+/// refers to other slots by index, which removes per-node allocation and lets
+/// the tree be copied around cheaply.
+#[derive(Debug, Clone, Copy, Default)]
+struct FlatNode {
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+    /// Set only on leaves.
+    symbol: Option<u8>,
+}
+
+/// Build the Huffman tree for a set of byte frequencies inside a flat arena.
+///
+/// The leaves occupy slots `0..freqs.len()`; every merge writes the new
+/// internal node into the next free slot and links its children's `parent`
+/// back to it. Returns the filled arena and the index of the root.
+fn build_flat_tree(freqs: &[(u8, i32)]) -> ([FlatNode; MAX_NODES], usize) {
+    let mut nodes = [FlatNode::default(); MAX_NODES];
+    let mut min_heap = BinaryHeap::new();
+    let mut next = 0;
+
+    for &(sym, count) in freqs {
+        nodes[next] = FlatNode {
+            symbol: Some(sym),
+            ..FlatNode::default()
+        };
+        // Reverse turns the max-heap into a min-heap; the index breaks ties.
+        min_heap.push(Reverse((count, next)));
+        next += 1;
+    }
+
+    while min_heap.len() > 1 {
+        let Reverse((count1, idx1)) = min_heap.pop().unwrap();
+        let Reverse((count2, idx2)) = min_heap.pop().unwrap();
+        let parent = next;
+        next += 1;
+        nodes[parent] = FlatNode {
+            left: Some(idx1),
+            right: Some(idx2),
+            ..FlatNode::default()
+        };
+        nodes[idx1].parent = Some(parent);
+        nodes[idx2].parent = Some(parent);
+        min_heap.push(Reverse((count1 + count2, parent)));
+    }
+
+    let root = min_heap.pop().unwrap().0 .1;
+    (nodes, root)
+}
+
+/// Read each leaf's code straight out of the flat arena by walking `parent`
+/// links up to the root and reversing the collected bits.
+fn build_flat_code_table(nodes: &[FlatNode], num_leaves: usize) -> HashMap<u8, String> {
+    let mut table = HashMap::new();
+
+    // A single-symbol tree is just one leaf; give it the one-bit code "0".
+    if num_leaves == 1 {
+        if let Some(sym) = nodes[0].symbol {
+            table.insert(sym, String::from("0"));
+        }
+        return table;
+    }
+
+    for leaf in 0..num_leaves {
+        let sym = nodes[leaf].symbol.unwrap();
+        let mut bits = Vec::new();
+        let mut cur = leaf;
+        while let Some(parent) = nodes[cur].parent {
+            // Left child is 0, right child is 1.
+            bits.push(if nodes[parent].right == Some(cur) { '1' } else { '0' });
+            cur = parent;
+        }
+        bits.reverse();
+        table.insert(sym, bits.into_iter().collect());
+    }
+
+    table
+}
+
+/// Count byte frequencies, returned as `(byte, count)` pairs ready to seed the
+/// flat tree.
+fn build_byte_freqs(data: &[u8]) -> Vec<(u8, i32)> {
+    let mut counts = HashMap::new();
+    for &b in data {
+        *counts.entry(b).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+/// Thread a byte code table into a flat arena so the streaming decoder can walk
+/// it bit-by-bit. Slot 0 is the root; internal nodes are carved out on demand.
+fn build_flat_tree_from_codes(codes: &HashMap<u8, String>) -> ([FlatNode; MAX_NODES], usize) {
+    let mut nodes = [FlatNode::default(); MAX_NODES];
+    let mut next = 1;
+
+    for (&sym, code) in codes {
+        let mut cur = 0;
+        let bits: Vec<bool> = code.chars().map(|c| c == '1').collect();
+        for (i, bit) in bits.iter().enumerate() {
+            let child = if *bit { nodes[cur].right } else { nodes[cur].left };
+            cur = match child {
+                Some(existing) => existing,
+                None => {
+                    let idx = next;
+                    next += 1;
+                    nodes[idx] = FlatNode { parent: Some(cur), ..FlatNode::default() };
+                    if *bit {
+                        nodes[cur].right = Some(idx);
+                    } else {
+                        nodes[cur].left = Some(idx);
+                    }
+                    idx
+                },
+            };
+            if i + 1 == bits.len() {
+                nodes[cur].symbol = Some(sym);
+            }
+        }
+    }
+
+    (nodes, 0)
+}
+
+/// Streaming Huffman encoder: feeds symbols through a precomputed code table
+/// and flushes packed bytes to the underlying writer as soon as eight bits
+/// accumulate, so only a single partial byte is ever held in memory.
+pub struct HuffmanEncoder<W: Write> {
+    out: W,
+    codes: HashMap<u8, String>,
+    bit_buf: u8,
+    num_bits: usize,
+}
+
+impl<W: Write> HuffmanEncoder<W> {
+    pub fn new(out: W, codes: HashMap<u8, String>) -> HuffmanEncoder<W> {
+        HuffmanEncoder { out, codes, bit_buf: 0, num_bits: 0 }
+    }
+
+    /// Build an encoder from canonical code lengths instead of an explicit
+    /// table.
+    pub fn from_canonical_lengths(out: W, lengths: &HashMap<u8, usize>) -> HuffmanEncoder<W> {
+        HuffmanEncoder::new(out, canonical_code_table(lengths))
+    }
+
+    /// Encode a single byte, flushing whole bytes as they fill up.
+    pub fn write_symbol(&mut self, sym: u8) -> io::Result<()> {
+        let code = self.codes[&sym].clone();
+        for bit in code.chars() {
+            self.push_bit(bit == '1')?;
+        }
+        Ok(())
+    }
+
+    /// Encode a run of bytes.
+    pub fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        for &b in data {
+            self.write_symbol(b)?;
+        }
+        Ok(())
+    }
+
+    fn push_bit(&mut self, bit: bool) -> io::Result<()> {
+        if bit {
+            self.bit_buf |= 1 << (7 - self.num_bits);
+        }
+        self.num_bits += 1;
+        if self.num_bits == 8 {
+            self.out.write_all(&[self.bit_buf])?;
+            self.bit_buf = 0;
+            self.num_bits = 0;
+        }
+        Ok(())
+    }
+
+    /// Flush any buffered bits (zero-padding the final byte) and return the
+    /// writer together with the number of real bits in that last byte.
+    pub fn finish(mut self) -> io::Result<(W, u8)> {
+        let last_bits = if self.num_bits == 0 {
+            8
+        } else {
+            let written = self.num_bits as u8;
+            self.out.write_all(&[self.bit_buf])?;
+            written
+        };
+        Ok((self.out, last_bits))
+    }
+}
+
+/// Streaming Huffman decoder: lazily pulls bytes from the reader and yields one
+/// decoded symbol at a time, traversing the decode tree bit-by-bit across byte
+/// boundaries.
+pub struct HuffmanDecoder<R: Read> {
+    input: R,
+    nodes: [FlatNode; MAX_NODES],
+    root: usize,
+    node: usize,
+    remaining: usize,
+    byte: u8,
+    bit_pos: u8,
+    buf: [u8; 1],
+}
+
+impl<R: Read> HuffmanDecoder<R> {
+    pub fn new(input: R, codes: &HashMap<u8, String>, num_symbols: usize) -> HuffmanDecoder<R> {
+        let (nodes, root) = build_flat_tree_from_codes(codes);
+        HuffmanDecoder {
+            input,
+            nodes,
+            root,
+            node: root,
+            remaining: num_symbols,
+            byte: 0,
+            // Force a read before the first bit is consumed.
+            bit_pos: 8,
+            buf: [0; 1],
+        }
+    }
+
+    /// Build a decoder from canonical code lengths instead of an explicit
+    /// table.
+    pub fn from_canonical_lengths(
+        input: R,
+        lengths: &HashMap<u8, usize>,
+        num_symbols: usize,
+    ) -> HuffmanDecoder<R> {
+        HuffmanDecoder::new(input, &canonical_code_table(lengths), num_symbols)
+    }
+}
+
+impl<R: Read> Iterator for HuffmanDecoder<R> {
+    type Item = io::Result<u8>;
+
+    fn next(&mut self) -> Option<io::Result<u8>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            if self.bit_pos == 8 {
+                match self.input.read(&mut self.buf) {
+                    Ok(0) => {
+                        return Some(Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "stream ended before all symbols were decoded",
+                        )));
+                    },
+                    Ok(_) => {
+                        self.byte = self.buf[0];
+                        self.bit_pos = 0;
+                    },
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            let bit = (self.byte >> (7 - self.bit_pos)) & 1 == 1;
+            self.bit_pos += 1;
+            let child = if bit { self.nodes[self.node].right } else { self.nodes[self.node].left };
+            self.node = child.expect("bitstream did not match any code");
+
+            if let Some(sym) = self.nodes[self.node].symbol {
+                self.node = self.root;
+                self.remaining -= 1;
+                return Some(Ok(sym));
+            }
+        }
+    }
 }
 
 fn build_freq_map(s: &str) -> HashMap<char, i32> {
@@ -92,22 +619,22 @@ fn build_huffman_tree(freq_map: &HashMap<char, i32>) -> Node {
     min_heap.pop().unwrap()
 }
 
-fn build_code_table(root: Node) -> HashMap<char, String> {
+fn build_code_table(root: &Node) -> HashMap<char, String> {
     let mut table = HashMap::new();
     let mut node_stack = Vec::new();
     node_stack.push((root, String::new()));
 
-    while !node_stack.is_empty() {
-        let (node, code) = node_stack.pop().unwrap();
+    while let Some((node, code)) = node_stack.pop() {
         match node.data {
-            NodeType::Internal { left_child, right_child } => {
-                let left_child = *left_child;
-                let right_child = *right_child;
-                node_stack.push((left_child, code.clone() + "0"));
-                node_stack.push((right_child, code + "1"));
+            NodeType::Internal { ref left_child, ref right_child } => {
+                node_stack.push((&**left_child, code.clone() + "0"));
+                node_stack.push((&**right_child, code + "1"));
             },
             NodeType::Leaf(sym) => {
                 // We've reached the end of a branch, add this code point to the table.
+                // A single-leaf tree has no branch bits, so give it the one-bit
+                // code "0" to keep the encode/decode loops well defined.
+                let code = if code.is_empty() { String::from("0") } else { code };
                 table.insert(sym, code);
             },
         }
@@ -116,11 +643,43 @@ fn build_code_table(root: Node) -> HashMap<char, String> {
     table
 }
 
+/// Assign canonical codes from code lengths alone: sort symbols by
+/// `(code_length, symbol_value)`, then hand out consecutive integers as codes,
+/// left-shifting whenever the length grows so that every code keeps its prefix
+/// property.
+fn canonical_code_table<S: Copy + Ord + Hash>(lengths: &HashMap<S, usize>) -> HashMap<S, String> {
+    let mut symbols: Vec<(S, usize)> = lengths.iter().map(|(&c, &l)| (c, l)).collect();
+    symbols.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+
+    let mut table = HashMap::new();
+    let mut code: u32 = 0;
+    let mut prev_len = 0;
+    for (sym, len) in symbols {
+        if prev_len != 0 && len > prev_len {
+            code <<= len - prev_len;
+        }
+        table.insert(sym, to_bit_string(code, len));
+        code += 1;
+        prev_len = len;
+    }
+    table
+}
+
+/// Render the low `len` bits of `code` as a bit string, most-significant-bit
+/// first.
+fn to_bit_string(code: u32, len: usize) -> String {
+    let mut s = String::with_capacity(len);
+    for i in (0..len).rev() {
+        s.push(if (code >> i) & 1 == 1 { '1' } else { '0' });
+    }
+    s
+}
+
 fn compress(s: &str, table: &HashMap<char, String>) -> SmallBitVec {
     s.chars()
         .map(|c| table.get(&c).unwrap())
         .flat_map(|s| s.chars())
-        .map(|c| if c == '1' { true } else { false })
+        .map(|c| c == '1')
         .collect()
 }
 
@@ -128,6 +687,22 @@ fn main() {
     let s = String::from("encode this huffman string");
     let huffman = huffman_code(&s);
     println!("{:?}", huffman.compressed);
+
+    // The byte coder builds a code table for the same input through the flat
+    // arena; ties may break differently, so its codes can differ from the above.
+    let freqs = build_byte_freqs(s.as_bytes());
+    let (nodes, _root) = build_flat_tree(&freqs);
+    let codes = build_flat_code_table(&nodes, freqs.len());
+    println!("{:?}", codes);
+
+    // Stream the same bytes through the chunked encoder/decoder.
+    let mut buf = Vec::new();
+    let mut encoder = HuffmanEncoder::new(&mut buf, codes.clone());
+    encoder.write(s.as_bytes()).unwrap();
+    encoder.finish().unwrap();
+    let decoder = HuffmanDecoder::new(io::Cursor::new(buf), &codes, s.len());
+    let decoded: io::Result<Vec<u8>> = decoder.collect();
+    println!("{:?}", decoded.unwrap() == s.as_bytes());
 }
 
 #[cfg(test)]
@@ -196,7 +771,7 @@ mod tests {
         let input = build_input();
         let freq_map = build_freq_map(&input);
         let huffman_tree = build_huffman_tree(&freq_map);
-        let code_table = build_code_table(huffman_tree);
+        let code_table = build_code_table(&huffman_tree);
         let mut correct_code_table = HashMap::new();
         correct_code_table.insert('f', String::from("0"));
         correct_code_table.insert('c', String::from("100"));
@@ -211,8 +786,64 @@ mod tests {
     #[test]
     fn encode_decode() {
         let input = "this should work";
-        let huffman = huffman_code(&input);
-        assert_eq!(input, huffman_decode(&huffman)); 
+        let huffman = huffman_code(input);
+        assert_eq!(input, huffman_decode(&huffman));
+    }
+
+    #[test]
+    fn to_from_bytes_round_trip() {
+        let input = "this should work";
+        let huffman = huffman_code(input);
+        let restored = HuffmanCode::from_bytes(&huffman.to_bytes());
+        assert_eq!(input, huffman_decode(&restored));
+    }
+
+    #[test]
+    fn canonical_encode_decode() {
+        let input = "this should work";
+        let huffman = huffman_code_canonical(input);
+        assert_eq!(input, huffman_decode(&huffman));
+    }
+
+    #[test]
+    fn flat_code_table() {
+        // Same frequencies as `code_table`, but over bytes and through the
+        // flat arena. The exact bit patterns may differ from the boxed tree
+        // (ties break by slot index), but the code lengths must match.
+        let freqs = vec![
+            (b'a', 5),
+            (b'b', 9),
+            (b'c', 12),
+            (b'd', 13),
+            (b'e', 16),
+            (b'f', 45),
+        ];
+        let (nodes, _root) = build_flat_tree(&freqs);
+        let table = build_flat_code_table(&nodes, freqs.len());
+
+        assert_eq!(table[&b'f'].len(), 1);
+        assert_eq!(table[&b'c'].len(), 3);
+        assert_eq!(table[&b'd'].len(), 3);
+        assert_eq!(table[&b'e'].len(), 3);
+        assert_eq!(table[&b'a'].len(), 4);
+        assert_eq!(table[&b'b'].len(), 4);
+    }
+
+    #[test]
+    fn streaming_round_trip() {
+        let data: &[u8] = b"stream this huffman payload across byte boundaries";
+        let freqs = build_byte_freqs(data);
+        let (nodes, _root) = build_flat_tree(&freqs);
+        let codes = build_flat_code_table(&nodes, freqs.len());
+
+        let mut buf = Vec::new();
+        let mut encoder = HuffmanEncoder::new(&mut buf, codes.clone());
+        encoder.write(data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = HuffmanDecoder::new(std::io::Cursor::new(buf), &codes, data.len());
+        let decoded: io::Result<Vec<u8>> = decoder.collect();
+        assert_eq!(&decoded.unwrap()[..], data);
     }
 
     fn build_input() -> String {